@@ -0,0 +1,153 @@
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::error::ComputeContextError;
+
+/// A shared `(device, queue)` pair, lazily created once per process and
+/// reused by every `FeatureExtractorPipeline` after that, instead of each
+/// call re-probing Vulkan/Metal/DX12 and spinning up its own device.
+#[derive(Clone)]
+pub struct ComputeContext {
+    pub device: Arc<wgpu::Device>,
+    pub queue: Arc<wgpu::Queue>,
+}
+
+static SHARED: OnceLock<RwLock<Option<ComputeContext>>> = OnceLock::new();
+
+/// Adapters are tried in this order; the first one exposing compute shader
+/// support wins. Whatever fallback/software adapter the backend can still
+/// offer is requested separately in [`ComputeContext::new_async`] and tried
+/// only after the adapters for both of these preferences.
+const POWER_PREFERENCE_ORDER: [Option<wgpu::PowerPreference>; 2] = [
+    Some(wgpu::PowerPreference::HighPerformance),
+    Some(wgpu::PowerPreference::LowPower),
+];
+
+impl ComputeContext {
+    /// Returns the process-wide compute context, creating it on first use.
+    ///
+    /// Native convenience wrapper around [`ComputeContext::shared_async`];
+    /// unavailable on `wasm32-unknown-unknown`, where adapter/device futures
+    /// only resolve on the browser's event loop and cannot be blocked on.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn shared() -> Result<Self, ComputeContextError> {
+        pollster::block_on(Self::shared_async())
+    }
+
+    /// Async counterpart of [`ComputeContext::shared`], suitable for
+    /// `wasm32-unknown-unknown`.
+    pub async fn shared_async() -> Result<Self, ComputeContextError> {
+        let slot = SHARED.get_or_init(|| RwLock::new(None));
+
+        if let Some(ctx) = slot.read().unwrap().as_ref() {
+            return Ok(ctx.clone());
+        }
+
+        let ctx = Self::new_async().await?;
+
+        let mut slot = slot.write().unwrap();
+        if let Some(ctx) = slot.as_ref() {
+            return Ok(ctx.clone());
+        }
+        *slot = Some(ctx.clone());
+        Ok(ctx)
+    }
+
+    /// Builds a brand-new context, bypassing the process-wide cache.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn new() -> Result<Self, ComputeContextError> {
+        pollster::block_on(Self::new_async())
+    }
+
+    /// Async counterpart of [`ComputeContext::new`], suitable for
+    /// `wasm32-unknown-unknown` where adapter/device futures only resolve on
+    /// the browser's event loop and cannot be blocked on.
+    pub async fn new_async() -> Result<Self, ComputeContextError> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor::default());
+
+        let mut candidates = Vec::new();
+        for power_preference in POWER_PREFERENCE_ORDER.into_iter().flatten() {
+            if let Some(adapter) = instance
+                .request_adapter(&wgpu::RequestAdapterOptions {
+                    power_preference,
+                    force_fallback_adapter: false,
+                    ..Default::default()
+                })
+                .await
+            {
+                candidates.push(adapter);
+            }
+        }
+        if let Some(adapter) = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::None,
+                force_fallback_adapter: true,
+                ..Default::default()
+            })
+            .await
+        {
+            candidates.push(adapter);
+        }
+
+        // Tracks the most recent `request_device` failure from a
+        // compute-capable adapter, so that if every candidate fails this
+        // way we can report *that* instead of the more generic
+        // `NoComputeCapableAdapter` (which would wrongly suggest no adapter
+        // supported compute at all).
+        let mut last_device_request_error = None;
+
+        for adapter in candidates {
+            if !adapter
+                .get_downlevel_capabilities()
+                .flags
+                .contains(wgpu::DownlevelFlags::COMPUTE_SHADERS)
+            {
+                continue;
+            }
+
+            let info = adapter.get_info();
+            let device_result = adapter
+                .request_device(&wgpu::DeviceDescriptor {
+                    label: None,
+                    required_features: wgpu::Features::empty(),
+                    required_limits: wgpu::Limits::downlevel_defaults(),
+                    memory_hints: wgpu::MemoryHints::MemoryUsage,
+                    trace: wgpu::Trace::Off,
+                })
+                .await;
+
+            let (device, queue) = match device_result {
+                Ok(pair) => pair,
+                Err(source) => {
+                    // This adapter claimed compute support but couldn't hand
+                    // out a device; fall through and try the next candidate
+                    // rather than giving up on the whole process.
+                    eprintln!(
+                        "adapter {:?} failed to produce a device: {source}",
+                        info.name
+                    );
+                    last_device_request_error = Some(ComputeContextError::DeviceRequestFailed {
+                        adapter_name: info.name,
+                        source,
+                    });
+                    continue;
+                }
+            };
+
+            println!("Running on Adapter: {info:#?}");
+
+            // Errors the pipeline doesn't bracket with its own push/pop
+            // error scope (e.g. a device-lost event) land here instead of
+            // becoming an uncaptured-error panic.
+            device.on_uncaptured_error(Box::new(|error| {
+                eprintln!("uncaptured wgpu error: {error}");
+            }));
+
+            return Ok(Self {
+                device: Arc::new(device),
+                queue: Arc::new(queue),
+            });
+        }
+
+        Err(last_device_request_error.unwrap_or(ComputeContextError::NoComputeCapableAdapter))
+    }
+}
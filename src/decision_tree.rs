@@ -0,0 +1,75 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+
+/// A single split node in a decision tree: compare `feature_idx` against
+/// `threshold` and recurse left/right, or read off `value` at a leaf.
+#[derive(Debug, Clone)]
+pub enum Node {
+    Split {
+        feature_idx: usize,
+        threshold: f32,
+        left: Box<Node>,
+        right: Box<Node>,
+    },
+    Leaf {
+        value: f32,
+    },
+}
+
+#[derive(Debug, Clone)]
+pub struct DecisionTree {
+    pub root: Node,
+}
+
+/// An ensemble of trees trained ilastik-style on a per-pixel feature vector.
+#[derive(Debug, Clone)]
+pub struct RandomForest {
+    trees: Vec<DecisionTree>,
+    highest_feature_idx: usize,
+}
+
+impl RandomForest {
+    /// Loads every tree serialized under `dir` (one file per tree).
+    pub fn from_dir(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref();
+        let mut trees = Vec::new();
+        for entry in std::fs::read_dir(dir)
+            .with_context(|| format!("reading forest directory {}", dir.display()))?
+        {
+            let entry = entry?;
+            let bytes = std::fs::read(entry.path())?;
+            let tree: DecisionTree = bincode::deserialize(&bytes)
+                .with_context(|| format!("parsing tree {}", entry.path().display()))?;
+            trees.push(tree);
+        }
+
+        let highest_feature_idx = trees
+            .iter()
+            .map(|tree| Self::highest_feature_idx_in(&tree.root))
+            .max()
+            .with_context(|| format!("no trees found in {}", dir.display()))?;
+
+        Ok(Self { trees, highest_feature_idx })
+    }
+
+    fn highest_feature_idx_in(node: &Node) -> usize {
+        match node {
+            Node::Leaf { .. } => 0,
+            Node::Split { feature_idx, left, right, .. } => (*feature_idx)
+                .max(Self::highest_feature_idx_in(left))
+                .max(Self::highest_feature_idx_in(right)),
+        }
+    }
+
+    /// The largest feature-vector index any tree in the forest reads from,
+    /// i.e. one less than the number of per-pixel feature channels the
+    /// pipeline must produce.
+    pub fn highest_feature_idx(&self) -> usize {
+        self.highest_feature_idx
+    }
+
+    pub fn trees(&self) -> &[DecisionTree] {
+        &self.trees
+    }
+}
@@ -0,0 +1,33 @@
+use thiserror::Error;
+
+/// Errors that can surface while acquiring a GPU compute context.
+#[derive(Debug, Error)]
+pub enum ComputeContextError {
+    #[error("no graphics backend exposes an adapter with compute shader support")]
+    NoComputeCapableAdapter,
+
+    #[error("failed to request a device from adapter {adapter_name:?}: {source}")]
+    DeviceRequestFailed {
+        adapter_name: String,
+        #[source]
+        source: wgpu::RequestDeviceError,
+    },
+}
+
+/// Errors that can surface while running a `FeatureExtractorPipeline`.
+///
+/// The dynamically generated WGSL comes from the `Expression<T>` codegen, so
+/// a forest with an unexpected feature index can produce a malformed shader;
+/// these variants let that surface as a typed error pointing at the
+/// offending pass instead of an uncaptured device-lost panic.
+#[derive(Debug, Error)]
+pub enum PipelineError {
+    #[error("GPU rejected pass {pass_name:?}: {message}")]
+    Validation { pass_name: String, message: String },
+
+    #[error("GPU ran out of memory during pass {pass_name:?}: {message}")]
+    OutOfMemory { pass_name: String, message: String },
+
+    #[error("readback buffer for pass {pass_name:?} could not be mapped: {message}")]
+    MapFailed { pass_name: String, message: String },
+}
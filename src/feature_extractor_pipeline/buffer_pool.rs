@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+/// A free-list buffer pool keyed by `(size, usage)`, so per-tile
+/// input/intermediate/output/readback buffers of a recurring shape are
+/// recycled across tiles and across repeated [`process`](super::pipeline::FeatureExtractorPipeline::process)
+/// calls instead of reallocated every time.
+pub struct BufferPool {
+    device: Arc<wgpu::Device>,
+    free: Mutex<HashMap<(u64, wgpu::BufferUsages), Vec<wgpu::Buffer>>>,
+}
+
+impl BufferPool {
+    pub fn new(device: Arc<wgpu::Device>) -> Self {
+        Self {
+            device,
+            free: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Takes a buffer of exactly `size` bytes and `usage` out of the free
+    /// list, allocating a fresh one only if none is available to reuse.
+    pub fn acquire(&self, label: &str, size: u64, usage: wgpu::BufferUsages) -> wgpu::Buffer {
+        let key = (size, usage);
+        if let Some(buffer) = self.free.lock().unwrap().get_mut(&key).and_then(Vec::pop) {
+            return buffer;
+        }
+        self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(label),
+            size,
+            usage,
+            mapped_at_creation: false,
+        })
+    }
+
+    /// Returns a buffer to the pool once the caller is done with it, so a
+    /// later [`acquire`](Self::acquire) of the same size/usage can reuse it.
+    pub fn release(&self, buffer: wgpu::Buffer) {
+        let key = (buffer.size(), buffer.usage());
+        self.free.lock().unwrap().entry(key).or_default().push(buffer);
+    }
+}
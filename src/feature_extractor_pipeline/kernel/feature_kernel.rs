@@ -0,0 +1,26 @@
+use crate::wgsl::{Expression, FVec4};
+
+/// How a [`FeatureKernel`]'s contribution is wired into the pipeline's
+/// generated shader.
+pub enum KernelPlan {
+    /// A separable kernel: a horizontal pass into the shared intermediate
+    /// buffer, then a vertical pass reading it into the output slot. Used by
+    /// kernels whose naive per-pixel cost would otherwise be O(KSIDE^2).
+    Separable {
+        horizontal: Expression<FVec4>,
+        vertical: Expression<FVec4>,
+    },
+    /// A kernel computed directly from the input image in a single dispatch.
+    Direct(Expression<FVec4>),
+}
+
+/// A per-pixel feature channel the GPU pipeline evaluates and hands to the
+/// forest. Implemented by [`GaussianBlur`](super::gaussian_blur::GaussianBlur)
+/// and the derivative-of-Gaussian feature bank (difference-of-Gaussians,
+/// gradient magnitude, Laplacian, Hessian/structure-tensor eigenvalues).
+pub trait FeatureKernel<const KSIDE: usize> {
+    /// `source`/`intermediate` name the `array<vec4<f32>>` storage bindings
+    /// already in scope, `coord` the `vec2<i32>` invocation pixel and
+    /// `dims` the image's `vec2<u32>` dimensions.
+    fn plan(&self, source: &str, intermediate: &str, coord: &str, dims: &str) -> KernelPlan;
+}
@@ -0,0 +1,126 @@
+use crate::feature_extractor_pipeline::clamped_coord;
+use crate::wgsl::{Expression, FVec4};
+
+use super::feature_kernel::{FeatureKernel, KernelPlan};
+
+/// A single-scale Gaussian smoothing feature: convolve the image with a
+/// `KSIDE`-tap Gaussian of the given `sigma`.
+///
+/// A 2D Gaussian is separable (`G(x, y) = g(x) * g(y)`), so this is applied
+/// as two 1D passes -- [`GaussianBlur::horizontal_expression`] then
+/// [`GaussianBlur::vertical_expression`] -- rather than one dense `KSIDE`x
+/// `KSIDE` convolution, cutting per-pixel work from O(KSIDE^2) to O(2*KSIDE).
+#[derive(Clone, Copy, Debug)]
+pub struct GaussianBlur<const KSIDE: usize> {
+    pub sigma: f32,
+}
+
+impl<const KSIDE: usize> GaussianBlur<KSIDE> {
+    /// The normalized, length-`KSIDE` 1D Gaussian weights for `self.sigma`,
+    /// with `weights_1d()[i]` at offset `i - radius`.
+    pub fn weights_1d(&self) -> [f32; KSIDE] {
+        let radius = (KSIDE / 2) as i32;
+        let mut weights = [0f32; KSIDE];
+        let mut sum = 0f32;
+        for (i, w) in weights.iter_mut().enumerate() {
+            let x = (i as i32 - radius) as f32;
+            let v = (-(x * x) / (2.0 * self.sigma * self.sigma)).exp();
+            *w = v;
+            sum += v;
+        }
+        for w in weights.iter_mut() {
+            *w /= sum;
+        }
+        weights
+    }
+
+    /// Emits the WGSL expression for the horizontal pass: a 1D convolution
+    /// along x, reading `source` (an `array<vec4<f32>>` storage binding
+    /// already in scope) and writing into an intermediate buffer.
+    pub fn horizontal_expression(&self, source: &str, coord: &str, dims: &str) -> Expression<FVec4> {
+        self.separable_expression(source, coord, dims, |dx| (dx, 0))
+    }
+
+    /// Emits the WGSL expression for the vertical pass: a 1D convolution
+    /// along y, reading the horizontal pass's intermediate buffer.
+    pub fn vertical_expression(&self, source: &str, coord: &str, dims: &str) -> Expression<FVec4> {
+        self.separable_expression(source, coord, dims, |dy| (0, dy))
+    }
+
+    fn separable_expression(
+        &self,
+        source: &str,
+        coord: &str,
+        dims: &str,
+        offset: impl Fn(i32) -> (i32, i32),
+    ) -> Expression<FVec4> {
+        let radius = (KSIDE / 2) as i32;
+        let weights = self.weights_1d();
+
+        let mut acc: Option<Expression<FVec4>> = None;
+        for (i, &w) in weights.iter().enumerate() {
+            if w == 0.0 {
+                continue;
+            }
+            let (dx, dy) = offset(i as i32 - radius);
+            let tap_coord = clamped_coord(&format!("{coord}.x"), &format!("{coord}.y"), dims, dx, dy);
+            let tap = Expression::<FVec4>::raw(format!(
+                "{source}[{tap_coord}.y * i32({dims}.x) + {tap_coord}.x]"
+            ));
+            let term = Expression::<f32>::from(w) * &tap;
+            acc = Some(match acc {
+                Some(a) => a + term,
+                None => term,
+            });
+        }
+        acc.expect("KSIDE is always > 0, so the loop produces at least one term")
+    }
+}
+
+impl<const KSIDE: usize> FeatureKernel<KSIDE> for GaussianBlur<KSIDE> {
+    fn plan(&self, source: &str, intermediate: &str, coord: &str, dims: &str) -> KernelPlan {
+        KernelPlan::Separable {
+            horizontal: self.horizontal_expression(source, coord, dims),
+            vertical: self.vertical_expression(intermediate, coord, dims),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weights_1d_sums_to_one() {
+        let blur = GaussianBlur::<9> { sigma: 1.6 };
+        let sum: f32 = blur.weights_1d().iter().sum();
+        assert!((sum - 1.0).abs() < 1e-6, "weights summed to {sum}, expected 1.0");
+    }
+
+    #[test]
+    fn weights_1d_is_symmetric_about_the_radius() {
+        let blur = GaussianBlur::<9> { sigma: 1.6 };
+        let weights = blur.weights_1d();
+        for i in 0..weights.len() {
+            assert!(
+                (weights[i] - weights[weights.len() - 1 - i]).abs() < 1e-6,
+                "weights[{i}] = {}, weights[{}] = {}",
+                weights[i],
+                weights.len() - 1 - i,
+                weights[weights.len() - 1 - i],
+            );
+        }
+    }
+
+    #[test]
+    fn weights_1d_peaks_at_the_center() {
+        let blur = GaussianBlur::<9> { sigma: 1.6 };
+        let weights = blur.weights_1d();
+        let center = weights.len() / 2;
+        for (i, &w) in weights.iter().enumerate() {
+            if i != center {
+                assert!(w < weights[center], "weights[{i}] = {w} >= center weight {}", weights[center]);
+            }
+        }
+    }
+}
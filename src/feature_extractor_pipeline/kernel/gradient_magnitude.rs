@@ -0,0 +1,27 @@
+use crate::wgsl::{Expression, FVec4};
+
+use super::feature_kernel::{FeatureKernel, KernelPlan};
+use super::scale_space::{scale_space_expression, DerivativeOrder};
+
+/// Width of the derivative window used for `Gx`/`Gy`, deliberately
+/// independent of this kernel's `KSIDE` type parameter -- see
+/// [`hessian_eigenvalues`](super::hessian_eigenvalues)'s identical constant
+/// for why reusing the pipeline's much larger shared `KSIDE` here would be
+/// wasteful for this kernel's small scale.
+const DERIVATIVE_KSIDE: usize = 9;
+
+/// Gradient magnitude `sqrt(Gx^2 + Gy^2)` from Gaussian first derivatives at
+/// the given scale, computed per image channel.
+#[derive(Clone, Copy, Debug)]
+pub struct GradientMagnitude {
+    pub sigma: f32,
+}
+
+impl<const KSIDE: usize> FeatureKernel<KSIDE> for GradientMagnitude {
+    fn plan(&self, source: &str, _intermediate: &str, coord: &str, dims: &str) -> KernelPlan {
+        let gx = scale_space_expression::<DERIVATIVE_KSIDE>(self.sigma, DerivativeOrder::First, DerivativeOrder::Zeroth, source, coord, dims);
+        let gy = scale_space_expression::<DERIVATIVE_KSIDE>(self.sigma, DerivativeOrder::Zeroth, DerivativeOrder::First, source, coord, dims);
+        let magnitude = Expression::<FVec4>::raw(format!("sqrt(({gx}) * ({gx}) + ({gy}) * ({gy}))"));
+        KernelPlan::Direct(magnitude)
+    }
+}
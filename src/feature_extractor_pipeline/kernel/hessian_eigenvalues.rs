@@ -0,0 +1,45 @@
+use super::feature_kernel::{FeatureKernel, KernelPlan};
+use super::scale_space::{scale_space_expression, symmetric_eigenvalues, DerivativeOrder};
+
+/// Width of the derivative window used for `Gxx`/`Gyy`/`Gxy`, deliberately
+/// independent of this kernel's `KSIDE` type parameter.
+///
+/// The pipeline's shared `KSIDE` is sized for the widest `GaussianBlur` in
+/// the bank; reusing it here would convolve this kernel's much smaller-scale
+/// derivatives through a dense window many times wider than their Gaussian
+/// factor actually needs (and `symmetric_eigenvalues` re-embeds each of
+/// `gxx`/`gyy`/`gxy` two to three times over into `major`/`minor`, since
+/// `Expression` is plain string concatenation with no common-subexpression
+/// elimination -- multiplying that oversizing further). Hessian sigmas are
+/// small in practice, so a modest fixed window is enough.
+const DERIVATIVE_KSIDE: usize = 9;
+
+/// Which root of the Hessian/structure-tensor eigenvalue pair a kernel
+/// instance contributes; `lambda+ >= lambda-`.
+#[derive(Clone, Copy, Debug)]
+pub enum EigenSlot {
+    Major,
+    Minor,
+}
+
+/// One eigenvalue (selected by `slot`) of the Hessian of Gaussian
+/// `[[Gxx, Gxy], [Gxy, Gyy]]` at the given scale. The two roots are
+/// typically registered as a pair of feature kernels, one per slot.
+#[derive(Clone, Copy, Debug)]
+pub struct HessianEigenvalues {
+    pub sigma: f32,
+    pub slot: EigenSlot,
+}
+
+impl<const KSIDE: usize> FeatureKernel<KSIDE> for HessianEigenvalues {
+    fn plan(&self, source: &str, _intermediate: &str, coord: &str, dims: &str) -> KernelPlan {
+        let gxx = scale_space_expression::<DERIVATIVE_KSIDE>(self.sigma, DerivativeOrder::Second, DerivativeOrder::Zeroth, source, coord, dims);
+        let gyy = scale_space_expression::<DERIVATIVE_KSIDE>(self.sigma, DerivativeOrder::Zeroth, DerivativeOrder::Second, source, coord, dims);
+        let gxy = scale_space_expression::<DERIVATIVE_KSIDE>(self.sigma, DerivativeOrder::First, DerivativeOrder::First, source, coord, dims);
+        let (major, minor) = symmetric_eigenvalues(&gxx, &gyy, &gxy);
+        KernelPlan::Direct(match self.slot {
+            EigenSlot::Major => major,
+            EigenSlot::Minor => minor,
+        })
+    }
+}
@@ -0,0 +1,23 @@
+use super::feature_kernel::{FeatureKernel, KernelPlan};
+use super::scale_space::{scale_space_expression, DerivativeOrder};
+
+/// Width of the derivative window used for `Gxx`/`Gyy`, deliberately
+/// independent of this kernel's `KSIDE` type parameter -- see
+/// [`hessian_eigenvalues`](super::hessian_eigenvalues)'s identical constant
+/// for why reusing the pipeline's much larger shared `KSIDE` here would be
+/// wasteful for this kernel's small scale.
+const DERIVATIVE_KSIDE: usize = 9;
+
+/// Laplacian of Gaussian `Gxx + Gyy` at the given scale.
+#[derive(Clone, Copy, Debug)]
+pub struct LaplacianOfGaussian {
+    pub sigma: f32,
+}
+
+impl<const KSIDE: usize> FeatureKernel<KSIDE> for LaplacianOfGaussian {
+    fn plan(&self, source: &str, _intermediate: &str, coord: &str, dims: &str) -> KernelPlan {
+        let gxx = scale_space_expression::<DERIVATIVE_KSIDE>(self.sigma, DerivativeOrder::Second, DerivativeOrder::Zeroth, source, coord, dims);
+        let gyy = scale_space_expression::<DERIVATIVE_KSIDE>(self.sigma, DerivativeOrder::Zeroth, DerivativeOrder::Second, source, coord, dims);
+        KernelPlan::Direct(gxx + gyy)
+    }
+}
@@ -0,0 +1,10 @@
+pub mod difference_of_gaussians;
+pub mod feature_kernel;
+pub mod gaussian_blur;
+pub mod gradient_magnitude;
+pub mod hessian_eigenvalues;
+pub mod laplacian_of_gaussian;
+pub mod scale_space;
+pub mod structure_tensor_eigenvalues;
+
+pub use feature_kernel::{FeatureKernel, KernelPlan};
@@ -0,0 +1,162 @@
+use crate::feature_extractor_pipeline::clamped_coord;
+use crate::wgsl::{Expression, FVec4};
+
+/// Which derivative of the 1D Gaussian a [`derivative_of_gaussian_1d`]
+/// profile represents. Closed only to the three orders this pipeline's
+/// feature kernels actually use (plain blur, gradient/structure tensor,
+/// Hessian), so there's no "unsupported order" case to handle at all.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DerivativeOrder {
+    Zeroth,
+    First,
+    Second,
+}
+
+/// The length-`KSIDE` 1D profile of a derivative-of-Gaussian kernel: the
+/// zeroth order is the Gaussian itself (normalized to sum to 1), first/
+/// second are its derivatives.
+pub fn derivative_of_gaussian_1d<const KSIDE: usize>(sigma: f32, order: DerivativeOrder) -> [f32; KSIDE] {
+    let radius = (KSIDE / 2) as i32;
+    let two_sigma_sq = 2.0 * sigma * sigma;
+    let mut weights = [0f32; KSIDE];
+    for (i, w) in weights.iter_mut().enumerate() {
+        let x = (i as i32 - radius) as f32;
+        let gaussian = (-(x * x) / two_sigma_sq).exp();
+        *w = match order {
+            DerivativeOrder::Zeroth => gaussian,
+            DerivativeOrder::First => -(x / (sigma * sigma)) * gaussian,
+            DerivativeOrder::Second => ((x * x) / (sigma.powi(4)) - 1.0 / (sigma * sigma)) * gaussian,
+        };
+    }
+    if order == DerivativeOrder::Zeroth {
+        let sum: f32 = weights.iter().sum();
+        for w in weights.iter_mut() {
+            *w /= sum;
+        }
+    }
+    weights
+}
+
+/// Emits the WGSL expression for convolving `source` with the 2D kernel
+/// `d^(order_x+order_y)/dx^order_x dy^order_y G(x, y; sigma)`, built as the
+/// outer product of two 1D derivative-of-Gaussian profiles.
+///
+/// Unlike [`GaussianBlur`](super::gaussian_blur::GaussianBlur)'s separable
+/// two-pass convolution, this is a single dense `KSIDE`x`KSIDE` pass: the
+/// derivative feature kernels call it with a small fixed window (9 taps a
+/// side) rather than the pipeline's shared `KSIDE`, so the dense shape stays
+/// affordable.
+pub fn scale_space_expression<const KSIDE: usize>(
+    sigma: f32,
+    order_x: DerivativeOrder,
+    order_y: DerivativeOrder,
+    source: &str,
+    coord: &str,
+    dims: &str,
+) -> Expression<FVec4> {
+    let radius = (KSIDE / 2) as i32;
+    let weights_x = derivative_of_gaussian_1d::<KSIDE>(sigma, order_x);
+    let weights_y = derivative_of_gaussian_1d::<KSIDE>(sigma, order_y);
+
+    let mut acc: Option<Expression<FVec4>> = None;
+    for (j, &wy) in weights_y.iter().enumerate() {
+        if wy == 0.0 {
+            continue;
+        }
+        for (i, &wx) in weights_x.iter().enumerate() {
+            let w = wx * wy;
+            if w == 0.0 {
+                continue;
+            }
+            let dx = i as i32 - radius;
+            let dy = j as i32 - radius;
+            let tap_coord = clamped_coord(&format!("{coord}.x"), &format!("{coord}.y"), dims, dx, dy);
+            let tap = Expression::<FVec4>::raw(format!(
+                "{source}[{tap_coord}.y * i32({dims}.x) + {tap_coord}.x]"
+            ));
+            let term = Expression::<f32>::from(w) * &tap;
+            acc = Some(match acc {
+                Some(a) => a + term,
+                None => term,
+            });
+        }
+    }
+    acc.expect("KSIDE is always > 0, so the loop produces at least one term")
+}
+
+/// Closed-form eigenvalues of the symmetric 2x2 matrix `[[axx, axy], [axy,
+/// ayy]]`, evaluated per image channel: `lambda+/- = (axx+ayy)/2 +/-
+/// sqrt(((axx-ayy)/2)^2 + axy^2)`, with `lambda+ >= lambda-`.
+pub fn symmetric_eigenvalues(
+    axx: &Expression<FVec4>,
+    ayy: &Expression<FVec4>,
+    axy: &Expression<FVec4>,
+) -> (Expression<FVec4>, Expression<FVec4>) {
+    let trace_half = Expression::<FVec4>::raw(format!("(({axx}) + ({ayy})) * 0.5"));
+    let half_diff = Expression::<FVec4>::raw(format!("(({axx}) - ({ayy})) * 0.5"));
+    let spread = Expression::<FVec4>::raw(format!(
+        "sqrt(({half_diff}) * ({half_diff}) + ({axy}) * ({axy}))"
+    ));
+    let major = Expression::<FVec4>::raw(format!("({trace_half}) + ({spread})"));
+    let minor = Expression::<FVec4>::raw(format!("({trace_half}) - ({spread})"));
+    (major, minor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derivative_of_gaussian_1d_zeroth_order_sums_to_one() {
+        let weights = derivative_of_gaussian_1d::<9>(1.6, DerivativeOrder::Zeroth);
+        let sum: f32 = weights.iter().sum();
+        assert!((sum - 1.0).abs() < 1e-6, "weights summed to {sum}, expected 1.0");
+    }
+
+    #[test]
+    fn derivative_of_gaussian_1d_zeroth_order_is_symmetric() {
+        let weights = derivative_of_gaussian_1d::<9>(1.6, DerivativeOrder::Zeroth);
+        for i in 0..weights.len() {
+            assert!((weights[i] - weights[weights.len() - 1 - i]).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn derivative_of_gaussian_1d_first_order_is_antisymmetric_and_zero_at_center() {
+        let weights = derivative_of_gaussian_1d::<9>(1.6, DerivativeOrder::First);
+        let center = weights.len() / 2;
+        assert!(weights[center].abs() < 1e-6, "expected ~0 at center, got {}", weights[center]);
+        for i in 0..weights.len() {
+            assert!(
+                (weights[i] + weights[weights.len() - 1 - i]).abs() < 1e-6,
+                "weights[{i}] = {}, -weights[{}] = {}",
+                weights[i],
+                weights.len() - 1 - i,
+                -weights[weights.len() - 1 - i],
+            );
+        }
+    }
+
+    #[test]
+    fn derivative_of_gaussian_1d_second_order_is_symmetric_and_negative_at_center() {
+        let weights = derivative_of_gaussian_1d::<9>(1.6, DerivativeOrder::Second);
+        let center = weights.len() / 2;
+        assert!(weights[center] < 0.0, "expected negative peak response, got {}", weights[center]);
+        for i in 0..weights.len() {
+            assert!((weights[i] - weights[weights.len() - 1 - i]).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn symmetric_eigenvalues_major_is_not_less_than_minor() {
+        let axx = Expression::<FVec4>::raw("vec4<f32>(3.0)");
+        let ayy = Expression::<FVec4>::raw("vec4<f32>(1.0)");
+        let axy = Expression::<FVec4>::raw("vec4<f32>(0.5)");
+        let (major, minor) = symmetric_eigenvalues(&axx, &ayy, &axy);
+        // These are WGSL codegen expressions, not evaluated numbers, but the
+        // generated formula must keep `+ spread` as major and `- spread` as
+        // minor so that major >= minor holds once the shader actually runs.
+        assert!(major.to_string().contains(") + (sqrt("));
+        assert!(minor.to_string().contains(") - (sqrt("));
+    }
+}
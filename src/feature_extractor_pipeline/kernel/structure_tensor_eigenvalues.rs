@@ -0,0 +1,85 @@
+use super::feature_kernel::{FeatureKernel, KernelPlan};
+use super::hessian_eigenvalues::EigenSlot;
+use super::scale_space::{derivative_of_gaussian_1d, scale_space_expression, symmetric_eigenvalues, DerivativeOrder};
+use crate::feature_extractor_pipeline::clamped_coord;
+use crate::wgsl::{Expression, FVec4};
+
+/// Width of the derivative window used for `Ix`/`Iy` here, and of the
+/// integration-scale smoothing window applied to their products, both
+/// deliberately independent of this kernel's `KSIDE` type parameter.
+///
+/// This kernel evaluates `Ix`/`Iy` once per integration tap, so its
+/// generated expression size is `INTEGRATION_KSIDE^2 * DERIVATIVE_KSIDE^2`
+/// terms; reusing the pipeline's shared `KSIDE` (sized for the much larger
+/// plain-blur bank) here would make that nesting intractable. Structure
+/// tensor sigmas are small in practice, so a modest fixed window is enough.
+const DERIVATIVE_KSIDE: usize = 9;
+const INTEGRATION_KSIDE: usize = 5;
+
+/// One eigenvalue (selected by `slot`) of the structure tensor
+/// `[[Ix^2, Ix*Iy], [Ix*Iy, Iy^2]]`, where the gradient products are
+/// themselves smoothed by a second, integration-scale Gaussian before the
+/// eigenvalues are taken. Without that second smoothing pass the matrix is
+/// the outer product of `(Ix, Iy)` with itself and is identically rank-1
+/// (its minor eigenvalue is ~0 everywhere and its major eigenvalue is just
+/// the squared gradient magnitude) -- the smoothing is what distinguishes
+/// the structure tensor from that plain gradient outer product.
+#[derive(Clone, Copy, Debug)]
+pub struct StructureTensorEigenvalues {
+    pub sigma: f32,
+    pub integration_sigma: f32,
+    pub slot: EigenSlot,
+}
+
+impl<const KSIDE: usize> FeatureKernel<KSIDE> for StructureTensorEigenvalues {
+    fn plan(&self, source: &str, _intermediate: &str, coord: &str, dims: &str) -> KernelPlan {
+        let radius = (INTEGRATION_KSIDE / 2) as i32;
+        let weights = derivative_of_gaussian_1d::<INTEGRATION_KSIDE>(self.integration_sigma, DerivativeOrder::Zeroth);
+
+        let mut ixx: Option<Expression<FVec4>> = None;
+        let mut iyy: Option<Expression<FVec4>> = None;
+        let mut ixy: Option<Expression<FVec4>> = None;
+
+        for (j, &wy) in weights.iter().enumerate() {
+            for (i, &wx) in weights.iter().enumerate() {
+                let w = wx * wy;
+                if w == 0.0 {
+                    continue;
+                }
+                let dx = i as i32 - radius;
+                let dy = j as i32 - radius;
+                let tap_coord = clamped_coord(&format!("{coord}.x"), &format!("{coord}.y"), dims, dx, dy);
+
+                let ix = scale_space_expression::<DERIVATIVE_KSIDE>(self.sigma, DerivativeOrder::First, DerivativeOrder::Zeroth, source, &format!("{tap_coord}"), dims);
+                let iy = scale_space_expression::<DERIVATIVE_KSIDE>(self.sigma, DerivativeOrder::Zeroth, DerivativeOrder::First, source, &format!("{tap_coord}"), dims);
+
+                let ixx_term = Expression::<f32>::from(w) * &Expression::<FVec4>::raw(format!("({ix}) * ({ix})"));
+                let iyy_term = Expression::<f32>::from(w) * &Expression::<FVec4>::raw(format!("({iy}) * ({iy})"));
+                let ixy_term = Expression::<f32>::from(w) * &Expression::<FVec4>::raw(format!("({ix}) * ({iy})"));
+
+                ixx = Some(match ixx {
+                    Some(acc) => acc + ixx_term,
+                    None => ixx_term,
+                });
+                iyy = Some(match iyy {
+                    Some(acc) => acc + iyy_term,
+                    None => iyy_term,
+                });
+                ixy = Some(match ixy {
+                    Some(acc) => acc + ixy_term,
+                    None => ixy_term,
+                });
+            }
+        }
+
+        let ixx = ixx.expect("INTEGRATION_KSIDE is always > 0, so the loop produces at least one term");
+        let iyy = iyy.expect("INTEGRATION_KSIDE is always > 0, so the loop produces at least one term");
+        let ixy = ixy.expect("INTEGRATION_KSIDE is always > 0, so the loop produces at least one term");
+
+        let (major, minor) = symmetric_eigenvalues(&ixx, &iyy, &ixy);
+        KernelPlan::Direct(match self.slot {
+            EigenSlot::Major => major,
+            EigenSlot::Minor => minor,
+        })
+    }
+}
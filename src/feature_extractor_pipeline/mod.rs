@@ -0,0 +1,17 @@
+pub mod buffer_pool;
+pub mod kernel;
+pub mod pipeline;
+pub mod tiling;
+
+use crate::wgsl::{Expression, IVec2};
+
+/// Builds the WGSL expression for the pixel coordinate `(x + dx, y + dy)`,
+/// clamped to `[0, dims - 1]` on each axis so convolution taps never read
+/// past the image border.
+pub(crate) fn clamped_coord(x: &str, y: &str, dims: &str, dx: i32, dy: i32) -> Expression<IVec2> {
+    let x_expr = Expression::<i32>::raw(format!("({x} + {dx})"))
+        .clamped(Expression::<i32>::raw("0"), Expression::<i32>::raw(format!("(i32({dims}.x) - 1)")));
+    let y_expr = Expression::<i32>::raw(format!("({y} + {dy})"))
+        .clamped(Expression::<i32>::raw("0"), Expression::<i32>::raw(format!("(i32({dims}.y) - 1)")));
+    Expression::<IVec2>::construct(x_expr, y_expr)
+}
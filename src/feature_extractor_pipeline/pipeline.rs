@@ -0,0 +1,583 @@
+use image::{ImageBuffer, Rgba};
+#[cfg(not(target_arch = "wasm32"))]
+use pollster::FutureExt;
+use std::sync::Arc;
+use wgpu::Extent3d;
+
+use crate::compute_context::ComputeContext;
+use crate::decision_tree::RandomForest;
+use crate::error::PipelineError;
+use crate::util::WorkgroupSize;
+use crate::wgsl::Wgsl;
+
+use super::buffer_pool::BufferPool;
+use super::kernel::{FeatureKernel, KernelPlan};
+use super::tiling::{tile_layout, Tile};
+
+/// Tile interiors default to this many pixels on a side when tiling kicks
+/// in; override with [`FeatureExtractorPipeline::with_tile_size`].
+const DEFAULT_TILE_SIZE: u32 = 1024;
+
+/// The compute pipeline(s) implementing one kernel's contribution: either a
+/// separable horizontal/vertical pass pair, or a single direct pass.
+enum KernelPasses {
+    Separable {
+        horizontal: wgpu::ComputePipeline,
+        vertical: wgpu::ComputePipeline,
+    },
+    Direct(wgpu::ComputePipeline),
+}
+
+/// Which entry point(s) [`build_shader_source`] emitted for one kernel, so
+/// [`compile_pipelines`] can build the matching [`KernelPasses`] without
+/// re-deriving the shape from the generated shader text.
+enum KernelShape {
+    Separable,
+    Direct,
+}
+
+/// Runs a heterogeneous bank of [`FeatureKernel`]s on the GPU, writing each
+/// kernel's `vec4<f32>` output into its own slot of the returned buffer.
+///
+/// Images that fit within `device.limits().max_texture_dimension_2d` on
+/// both axes, and whose storage buffers (every resource here is a buffer,
+/// not a texture) fit within `max_storage_buffer_binding_size` and
+/// `max_buffer_size`, are processed in one dispatch per pass. Larger images
+/// are automatically split into overlapping tiles (see
+/// [`tiling`](super::tiling)), each run through the same passes
+/// independently and stitched back together; the halo a tile is padded by
+/// is exactly `(KSIDE - 1) / 2`, the convolution radius, so tiling never
+/// changes a pixel's result. Per-tile buffers are recycled via a
+/// [`BufferPool`] instead of reallocated per tile.
+pub struct FeatureExtractorPipeline<const KSIDE: usize> {
+    context: ComputeContext,
+    workgroup_size: WorkgroupSize,
+    num_kernels: usize,
+    img_extent: Extent3d,
+    tile_size: u32,
+    passes: Vec<KernelPasses>,
+    bind_group_layout: wgpu::BindGroupLayout,
+    buffer_pool: Arc<BufferPool>,
+}
+
+impl<const KSIDE: usize> FeatureExtractorPipeline<KSIDE> {
+    /// Wraps the caller's own `(device, queue)` in a [`ComputeContext`] used
+    /// by this pipeline alone. Prefer
+    /// [`FeatureExtractorPipeline::from_context`] with a shared
+    /// [`ComputeContext`] when constructing many pipelines in one process.
+    pub fn new(
+        device: wgpu::Device,
+        queue: wgpu::Queue,
+        workgroup_size: WorkgroupSize,
+        kernels: Vec<Box<dyn FeatureKernel<KSIDE>>>,
+        forest: &RandomForest,
+        img_extent: Extent3d,
+    ) -> Self {
+        Self::from_context(
+            ComputeContext {
+                device: std::sync::Arc::new(device),
+                queue: std::sync::Arc::new(queue),
+            },
+            workgroup_size,
+            kernels,
+            forest,
+            img_extent,
+        )
+    }
+
+    /// Builds the pipeline against an already-acquired, possibly shared,
+    /// [`ComputeContext`] instead of creating a fresh device for every call.
+    pub fn from_context(
+        context: ComputeContext,
+        workgroup_size: WorkgroupSize,
+        kernels: Vec<Box<dyn FeatureKernel<KSIDE>>>,
+        forest: &RandomForest,
+        img_extent: Extent3d,
+    ) -> Self {
+        assert!(
+            forest.highest_feature_idx() < kernels.len(),
+            "forest references feature index {} but only {} kernel(s) were provided",
+            forest.highest_feature_idx(),
+            kernels.len(),
+        );
+
+        let num_kernels = kernels.len();
+        let (shader_source, shapes) = build_shader_source(&kernels);
+        let (passes, bind_group_layout) = compile_pipelines(&context.device, &shapes, &shader_source);
+        let buffer_pool = Arc::new(BufferPool::new(context.device.clone()));
+
+        Self {
+            context,
+            workgroup_size,
+            num_kernels,
+            img_extent,
+            tile_size: DEFAULT_TILE_SIZE,
+            passes,
+            bind_group_layout,
+            buffer_pool,
+        }
+    }
+
+    /// Overrides the default tile interior size (see [`DEFAULT_TILE_SIZE`])
+    /// used when [`Self::fits_in_one_dispatch`] is false and tiling kicks in.
+    /// Still clamped down to [`Self::max_tile_interior_size`] at tiling time,
+    /// so this can only shrink tiles further, not grow a tile's own buffers
+    /// past the device's storage buffer limits.
+    pub fn with_tile_size(mut self, tile_size: u32) -> Self {
+        self.tile_size = tile_size;
+        self
+    }
+
+    /// The most pixels a single [`Self::run_passes`] dispatch can produce
+    /// output for without its output buffer (the largest of the three, at
+    /// `num_pixels * num_kernels * 16` bytes) exceeding the device's
+    /// `max_storage_buffer_binding_size`/`max_buffer_size`. Every resource in
+    /// this pipeline is a storage buffer rather than a texture, so these --
+    /// not `max_texture_dimension_2d` -- are what ordinary image sizes with a
+    /// large kernel bank actually run into.
+    fn max_storage_pixels(&self) -> u64 {
+        let limits = self.context.device.limits();
+        let max_buffer_bytes = (limits.max_storage_buffer_binding_size as u64).min(limits.max_buffer_size);
+        max_buffer_bytes / (self.num_kernels as u64 * std::mem::size_of::<[f32; 4]>() as u64)
+    }
+
+    /// Whether `self.img_extent` can be run through [`Self::run_passes`] in
+    /// a single dispatch: both its texture-dimension extent and its storage
+    /// buffers (see [`Self::max_storage_pixels`]) have to fit within the
+    /// device's limits.
+    fn fits_in_one_dispatch(&self) -> bool {
+        let limits = self.context.device.limits();
+        let max_dim = limits.max_texture_dimension_2d;
+        if self.img_extent.width > max_dim || self.img_extent.height > max_dim {
+            return false;
+        }
+        let num_pixels = self.img_extent.width as u64 * self.img_extent.height as u64;
+        num_pixels <= self.max_storage_pixels()
+    }
+
+    /// The largest tile interior size, in pixels per side, whose *padded*
+    /// extent (interior plus [`Self::halo`] on every side, the extent
+    /// [`Self::run_passes`] actually allocates buffers for) still fits
+    /// within [`Self::max_storage_pixels`]. [`Self::process_tiled`] clamps
+    /// `self.tile_size` to this bound, so a tile's own buffers can never
+    /// blow the same per-dispatch limits whole-image processing just
+    /// avoided by tiling in the first place.
+    fn max_tile_interior_size(&self) -> u32 {
+        let max_padded_side = (self.max_storage_pixels() as f64).sqrt() as u32;
+        max_padded_side.saturating_sub(2 * Self::halo())
+    }
+
+    /// Convolution taps reach this many pixels past a kernel's center, so a
+    /// tile must be padded by this much on every side to match the
+    /// whole-image result exactly at its borders.
+    const fn halo() -> u32 {
+        ((KSIDE - 1) / 2) as u32
+    }
+
+    /// Native convenience wrapper around [`Self::process_async`]. On native
+    /// targets the adapter/buffer-mapping futures it awaits resolve
+    /// immediately, so blocking on them is harmless; this path does not
+    /// exist on `wasm32-unknown-unknown`, where those futures only resolve
+    /// from the browser's event loop and must be awaited, not blocked on.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn process(&self, image: &ImageBuffer<Rgba<u8>, Vec<u8>>) -> Result<Vec<[f32; 4]>, PipelineError> {
+        self.process_async(image).block_on()
+    }
+
+    pub async fn process_async(&self, image: &ImageBuffer<Rgba<u8>, Vec<u8>>) -> Result<Vec<[f32; 4]>, PipelineError> {
+        if self.fits_in_one_dispatch() {
+            let pixels = to_f32_pixels(image);
+            return self.run_passes(&pixels, self.img_extent).await;
+        }
+        self.process_tiled(image).await
+    }
+
+    /// Splits `image` into tiles no larger than `self.tile_size` on a side
+    /// (further clamped to [`Self::max_tile_interior_size`], so a tile's own
+    /// buffers stay within the device's storage buffer limits regardless of
+    /// `num_kernels`), each padded by [`Self::halo`] pixels of context, runs
+    /// every tile through the same passes
+    /// [`process_async`](Self::process_async) would use directly, then
+    /// copies each tile's interior region back into a full-size result
+    /// buffer.
+    async fn process_tiled(&self, image: &ImageBuffer<Rgba<u8>, Vec<u8>>) -> Result<Vec<[f32; 4]>, PipelineError> {
+        let width = self.img_extent.width;
+        let height = self.img_extent.height;
+        let num_pixels = (width * height) as usize;
+
+        let tile_size = self.tile_size.min(self.max_tile_interior_size());
+        assert!(
+            tile_size > 0,
+            "num_kernels={} leaves no tile interior size whose padded buffers fit this device's storage buffer limits",
+            self.num_kernels,
+        );
+
+        let tiles = tile_layout(width, height, tile_size, Self::halo());
+        let mut result = vec![[0f32; 4]; num_pixels * self.num_kernels];
+
+        for tile in tiles {
+            let tile_pixels = crop_f32_pixels(image, &tile);
+            let tile_output = self.run_passes(&tile_pixels, tile.padded_extent).await?;
+            stitch_tile(&tile_output, tile.padded_extent, &tile, width, self.num_kernels, &mut result);
+        }
+
+        Ok(result)
+    }
+
+    /// Runs every kernel's pass(es) over `pixels` (a `width`x`height`
+    /// `extent` of already-normalized RGBA floats) and reads back the
+    /// `vec4<f32>` result for each kernel, in its own contiguous slot of the
+    /// returned buffer. Shared by the whole-image and tiled code paths, with
+    /// all GPU buffers borrowed from `self.buffer_pool` and returned to it
+    /// before this call completes.
+    async fn run_passes(&self, pixels: &[[f32; 4]], extent: Extent3d) -> Result<Vec<[f32; 4]>, PipelineError> {
+        let device = &self.context.device;
+        let queue = &self.context.queue;
+        let pool = &self.buffer_pool;
+
+        let num_pixels = (extent.width * extent.height) as usize;
+        let pixel_bytes = (num_pixels * std::mem::size_of::<[f32; 4]>()) as u64;
+
+        let input_buffer = pool.acquire(
+            "feature-extractor input",
+            pixel_bytes,
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_DST,
+        );
+        queue.write_buffer(&input_buffer, 0, bytemuck::cast_slice(pixels));
+
+        // Reused across every separable kernel: the horizontal pass for
+        // kernel N+1 only starts once kernel N's vertical pass has read
+        // this buffer, since both live in the same command encoder in
+        // submission order.
+        let intermediate_buffer = pool.acquire("feature-extractor intermediate", pixel_bytes, wgpu::BufferUsages::STORAGE);
+
+        let output_bytes = pixel_bytes * self.num_kernels as u64;
+        let output_buffer = pool.acquire(
+            "feature-extractor output",
+            output_bytes,
+            wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+        );
+
+        let dims_buffer = pool.acquire(
+            "feature-extractor dims",
+            std::mem::size_of::<[u32; 2]>() as u64,
+            wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        );
+        queue.write_buffer(&dims_buffer, 0, bytemuck::cast_slice(&[extent.width, extent.height]));
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("feature-extractor bind group"),
+            layout: &self.bind_group_layout,
+            entries: &[
+                wgpu::BindGroupEntry { binding: 0, resource: input_buffer.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 1, resource: intermediate_buffer.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 2, resource: output_buffer.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 3, resource: dims_buffer.as_entire_binding() },
+            ],
+        });
+
+        const PASS_NAME: &str = "feature-extractor pass";
+
+        device.push_error_scope(wgpu::ErrorFilter::Validation);
+        device.push_error_scope(wgpu::ErrorFilter::OutOfMemory);
+
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("feature-extractor encoder"),
+        });
+        let workgroups_x = extent.width.div_ceil(self.workgroup_size.x);
+        let workgroups_y = extent.height.div_ceil(self.workgroup_size.y);
+        for kernel_passes in &self.passes {
+            let dispatch = |pass_label: &str, pipeline: &wgpu::ComputePipeline, encoder: &mut wgpu::CommandEncoder| {
+                let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                    label: Some(pass_label),
+                    timestamp_writes: None,
+                });
+                pass.set_pipeline(pipeline);
+                pass.set_bind_group(0, &bind_group, &[]);
+                pass.dispatch_workgroups(workgroups_x, workgroups_y, 1);
+            };
+            match kernel_passes {
+                KernelPasses::Separable { horizontal, vertical } => {
+                    dispatch("feature-extractor horizontal pass", horizontal, &mut encoder);
+                    dispatch("feature-extractor vertical pass", vertical, &mut encoder);
+                }
+                KernelPasses::Direct(pipeline) => {
+                    dispatch("feature-extractor direct pass", pipeline, &mut encoder);
+                }
+            }
+        }
+
+        let readback = pool.acquire(
+            "feature-extractor readback",
+            output_buffer.size(),
+            wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+        );
+        encoder.copy_buffer_to_buffer(&output_buffer, 0, &readback, 0, output_buffer.size());
+
+        queue.submit(Some(encoder.finish()));
+
+        // Out-of-memory scope was pushed last, so it pops first.
+        let oom_error = device.pop_error_scope().await;
+        let validation_error = device.pop_error_scope().await;
+        if let Some(error) = validation_error.or(oom_error) {
+            pool.release(input_buffer);
+            pool.release(intermediate_buffer);
+            pool.release(output_buffer);
+            pool.release(dims_buffer);
+            pool.release(readback);
+            return Err(map_device_error(PASS_NAME, error));
+        }
+
+        let slice = readback.slice(..);
+        let (tx, rx) = futures_channel::oneshot::channel();
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = tx.send(result);
+        });
+        // On native this is a courtesy poke; the browser drives polling for
+        // us on wasm32, where `device.poll` is a no-op.
+        device.poll(wgpu::Maintain::Wait);
+        let map_result = rx.await.map_err(|_| PipelineError::MapFailed {
+            pass_name: PASS_NAME.to_string(),
+            message: "map_async callback dropped".to_string(),
+        });
+
+        let result = match map_result.and_then(|inner| {
+            inner.map_err(|e| PipelineError::MapFailed {
+                pass_name: PASS_NAME.to_string(),
+                message: e.to_string(),
+            })
+        }) {
+            Ok(()) => {
+                let data = slice.get_mapped_range();
+                let result: Vec<[f32; 4]> = bytemuck::cast_slice(&data).to_vec();
+                drop(data);
+                Ok(result)
+            }
+            Err(error) => Err(error),
+        };
+
+        readback.unmap();
+        pool.release(input_buffer);
+        pool.release(intermediate_buffer);
+        pool.release(output_buffer);
+        pool.release(dims_buffer);
+        pool.release(readback);
+
+        result
+    }
+}
+
+/// Converts a whole image to the normalized `vec4<f32>` layout the shader
+/// reads, row-major.
+fn to_f32_pixels(image: &ImageBuffer<Rgba<u8>, Vec<u8>>) -> Vec<[f32; 4]> {
+    image.pixels().map(|p| p.0.map(|c| c as f32 / 255.0)).collect()
+}
+
+/// Crops and converts just `tile.padded_extent`'s region of `image` to the
+/// normalized `vec4<f32>` layout.
+fn crop_f32_pixels(image: &ImageBuffer<Rgba<u8>, Vec<u8>>, tile: &Tile) -> Vec<[f32; 4]> {
+    let (tile_x, tile_y) = tile.padded_offset;
+    let Extent3d { width, height, .. } = tile.padded_extent;
+    let mut pixels = Vec::with_capacity((width * height) as usize);
+    for row in 0..height {
+        for col in 0..width {
+            let pixel = image.get_pixel(tile_x + col, tile_y + row);
+            pixels.push(pixel.0.map(|c| c as f32 / 255.0));
+        }
+    }
+    pixels
+}
+
+/// Copies `tile`'s interior region out of `tile_output` (sized for
+/// `tile_extent`, one contiguous `vec4<f32>` slot per kernel) into the
+/// matching rectangle of `result` (sized for the full image, `image_width`
+/// wide, `num_kernels` slots).
+fn stitch_tile(
+    tile_output: &[[f32; 4]],
+    tile_extent: Extent3d,
+    tile: &Tile,
+    image_width: u32,
+    num_kernels: usize,
+    result: &mut [[f32; 4]],
+) {
+    let num_pixels = result.len() / num_kernels;
+    let tile_num_pixels = (tile_extent.width * tile_extent.height) as usize;
+    let (interior_x_in_tile, interior_y_in_tile) = tile.interior_offset_in_tile();
+    let (interior_width, interior_height) = tile.interior_extent;
+    let (interior_x, interior_y) = tile.interior_offset;
+
+    for kernel_idx in 0..num_kernels {
+        let tile_slot = &tile_output[kernel_idx * tile_num_pixels..(kernel_idx + 1) * tile_num_pixels];
+        let result_slot = &mut result[kernel_idx * num_pixels..(kernel_idx + 1) * num_pixels];
+        for row in 0..interior_height {
+            let tile_row_start = ((interior_y_in_tile + row) * tile_extent.width + interior_x_in_tile) as usize;
+            let result_row_start = ((interior_y + row) * image_width + interior_x) as usize;
+            result_slot[result_row_start..result_row_start + interior_width as usize]
+                .copy_from_slice(&tile_slot[tile_row_start..tile_row_start + interior_width as usize]);
+        }
+    }
+}
+
+fn map_device_error(pass_name: &str, error: wgpu::Error) -> PipelineError {
+    let message = error.to_string();
+    match error {
+        wgpu::Error::OutOfMemory { .. } => PipelineError::OutOfMemory {
+            pass_name: pass_name.to_string(),
+            message,
+        },
+        wgpu::Error::Validation { .. } | wgpu::Error::Internal { .. } => PipelineError::Validation {
+            pass_name: pass_name.to_string(),
+            message,
+        },
+    }
+}
+
+/// Emits one entry point per kernel: separable kernels get a
+/// `horizontal_N`/`vertical_N` pair routed through the shared `intermediate`
+/// buffer, direct kernels get a single `direct_N` entry point writing
+/// straight into `output`'s `N`th feature slot. Also returns each kernel's
+/// [`KernelShape`], in kernel order, so [`compile_pipelines`] knows which
+/// entry points to build pipelines for without re-parsing the shader text.
+fn build_shader_source<const KSIDE: usize>(kernels: &[Box<dyn FeatureKernel<KSIDE>>]) -> (String, Vec<KernelShape>) {
+    let mut entry_points = String::new();
+    let mut shapes = Vec::with_capacity(kernels.len());
+    for (idx, kernel) in kernels.iter().enumerate() {
+        match kernel.plan("input", "intermediate", "coord", "dims") {
+            KernelPlan::Separable { horizontal, vertical } => {
+                shapes.push(KernelShape::Separable);
+                entry_points.push_str(&format!(
+                    r#"
+@compute @workgroup_size(16, 16, 1)
+fn horizontal_{idx}(@builtin(global_invocation_id) invocation_id: vec3<u32>) {{
+    if (invocation_id.x >= dims.width || invocation_id.y >= dims.height) {{
+        return;
+    }}
+    let coord = vec2<i32>(i32(invocation_id.x), i32(invocation_id.y));
+    let pixel_idx = invocation_id.y * dims.width + invocation_id.x;
+    intermediate[pixel_idx] = {horizontal};
+}}
+
+@compute @workgroup_size(16, 16, 1)
+fn vertical_{idx}(@builtin(global_invocation_id) invocation_id: vec3<u32>) {{
+    if (invocation_id.x >= dims.width || invocation_id.y >= dims.height) {{
+        return;
+    }}
+    let coord = vec2<i32>(i32(invocation_id.x), i32(invocation_id.y));
+    let pixel_idx = invocation_id.y * dims.width + invocation_id.x;
+    let out_idx = {idx}u * dims.width * dims.height + pixel_idx;
+    output[out_idx] = {vertical};
+}}
+"#,
+                ));
+            }
+            KernelPlan::Direct(expression) => {
+                shapes.push(KernelShape::Direct);
+                entry_points.push_str(&format!(
+                    r#"
+@compute @workgroup_size(16, 16, 1)
+fn direct_{idx}(@builtin(global_invocation_id) invocation_id: vec3<u32>) {{
+    if (invocation_id.x >= dims.width || invocation_id.y >= dims.height) {{
+        return;
+    }}
+    let coord = vec2<i32>(i32(invocation_id.x), i32(invocation_id.y));
+    let pixel_idx = invocation_id.y * dims.width + invocation_id.x;
+    let out_idx = {idx}u * dims.width * dims.height + pixel_idx;
+    output[out_idx] = {expression};
+}}
+"#,
+                ));
+            }
+        }
+    }
+
+    let shader_source = format!(
+        r#"
+struct Dims {{
+    width: u32,
+    height: u32,
+}}
+
+@group(0) @binding(0) var<storage, read> input: array<vec4<f32>>;
+@group(0) @binding(1) var<storage, read_write> intermediate: array<vec4<f32>>;
+@group(0) @binding(2) var<storage, read_write> output: array<vec4<f32>>;
+@group(0) @binding(3) var<uniform> dims: Dims;
+{entry_points}
+"#,
+    );
+    (shader_source, shapes)
+}
+
+/// Builds one [`KernelPasses`] per entry in `shapes`, in order, from the
+/// `horizontal_N`/`vertical_N`/`direct_N` entry points [`build_shader_source`]
+/// emitted into `shader_source` for the same kernel bank.
+fn compile_pipelines(
+    device: &wgpu::Device,
+    shapes: &[KernelShape],
+    shader_source: &str,
+) -> (Vec<KernelPasses>, wgpu::BindGroupLayout) {
+    let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: Some("feature-extractor shader"),
+        source: wgpu::ShaderSource::Wgsl(shader_source.into()),
+    });
+
+    let storage_entry = |binding: u32, read_only: bool| wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStages::COMPUTE,
+        ty: wgpu::BindingType::Buffer {
+            ty: wgpu::BufferBindingType::Storage { read_only },
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
+    };
+
+    let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some("feature-extractor bind group layout"),
+        entries: &[
+            storage_entry(0, true),
+            storage_entry(1, false),
+            storage_entry(2, false),
+            wgpu::BindGroupLayoutEntry {
+                binding: 3,
+                visibility: wgpu::ShaderStages::COMPUTE,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
+        ],
+    });
+
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some("feature-extractor pipeline layout"),
+        bind_group_layouts: &[&bind_group_layout],
+        push_constant_ranges: &[],
+    });
+
+    let make_pipeline = |entry_point: String| {
+        device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+            label: Some(&entry_point),
+            layout: Some(&pipeline_layout),
+            module: &shader,
+            entry_point: Some(&entry_point),
+            compilation_options: wgpu::PipelineCompilationOptions::default(),
+            cache: None,
+        })
+    };
+
+    let passes = shapes
+        .iter()
+        .enumerate()
+        .map(|(idx, shape)| match shape {
+            KernelShape::Separable => KernelPasses::Separable {
+                horizontal: make_pipeline(format!("horizontal_{idx}")),
+                vertical: make_pipeline(format!("vertical_{idx}")),
+            },
+            KernelShape::Direct => KernelPasses::Direct(make_pipeline(format!("direct_{idx}"))),
+        })
+        .collect();
+
+    (passes, bind_group_layout)
+}
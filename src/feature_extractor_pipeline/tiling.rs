@@ -0,0 +1,130 @@
+use wgpu::Extent3d;
+
+/// One tile of a larger image being processed piecewise: `padded_extent` is
+/// the region actually handed to the pipeline, expanded by a halo of
+/// convolution-radius pixels beyond `interior_offset`/`interior_extent` (and
+/// clamped at the image border) so taps near a tile's edge read real
+/// neighboring pixels instead of this tile's own clamped border.
+#[derive(Clone, Copy, Debug)]
+pub struct Tile {
+    pub padded_offset: (u32, u32),
+    pub padded_extent: Extent3d,
+    pub interior_offset: (u32, u32),
+    pub interior_extent: (u32, u32),
+}
+
+impl Tile {
+    /// Where [`interior_offset`](Self::interior_offset) falls within this
+    /// tile's own `padded_extent`-local coordinate space, i.e. the offset
+    /// callers should read the interior back out of once this tile's
+    /// `padded_extent` region has been processed.
+    pub fn interior_offset_in_tile(&self) -> (u32, u32) {
+        (
+            self.interior_offset.0 - self.padded_offset.0,
+            self.interior_offset.1 - self.padded_offset.1,
+        )
+    }
+}
+
+/// Splits a `width`x`height` image into tiles whose interior is at most
+/// `tile_size` pixels on a side, each padded by `halo` pixels of context on
+/// every side (clamped at the image border), so a convolution kernel with
+/// radius `halo` produces the exact same result per-pixel as it would
+/// running over the whole image at once.
+pub fn tile_layout(width: u32, height: u32, tile_size: u32, halo: u32) -> Vec<Tile> {
+    let mut tiles = Vec::new();
+    let mut y = 0;
+    while y < height {
+        let interior_h = tile_size.min(height - y);
+        let mut x = 0;
+        while x < width {
+            let interior_w = tile_size.min(width - x);
+
+            let padded_offset = (x.saturating_sub(halo), y.saturating_sub(halo));
+            let padded_width = (x + interior_w + halo).min(width) - padded_offset.0;
+            let padded_height = (y + interior_h + halo).min(height) - padded_offset.1;
+
+            tiles.push(Tile {
+                padded_offset,
+                padded_extent: Extent3d {
+                    width: padded_width,
+                    height: padded_height,
+                    depth_or_array_layers: 1,
+                },
+                interior_offset: (x, y),
+                interior_extent: (interior_w, interior_h),
+            });
+
+            x += interior_w;
+        }
+        y += interior_h;
+    }
+    tiles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_tile_when_image_fits_without_splitting() {
+        let tiles = tile_layout(100, 80, 1024, 8);
+        assert_eq!(tiles.len(), 1);
+        assert_eq!(tiles[0].interior_offset, (0, 0));
+        assert_eq!(tiles[0].interior_extent, (100, 80));
+    }
+
+    #[test]
+    fn interiors_exactly_cover_the_image_with_no_overlap() {
+        let width = 100;
+        let height = 90;
+        let tiles = tile_layout(width, height, 32, 8);
+        assert!(tiles.len() > 1, "expected the image to actually be split into multiple tiles");
+
+        let mut covered = vec![false; (width * height) as usize];
+        for tile in &tiles {
+            let (ox, oy) = tile.interior_offset;
+            let (iw, ih) = tile.interior_extent;
+            for y in oy..oy + ih {
+                for x in ox..ox + iw {
+                    let idx = (y * width + x) as usize;
+                    assert!(!covered[idx], "pixel ({x}, {y}) covered by more than one tile's interior");
+                    covered[idx] = true;
+                }
+            }
+        }
+        assert!(covered.iter().all(|&c| c), "every pixel must be covered by exactly one tile's interior");
+    }
+
+    #[test]
+    fn padded_extent_includes_halo_but_is_clamped_at_the_image_border() {
+        let width = 100;
+        let height = 100;
+        let halo = 8;
+        let tiles = tile_layout(width, height, 32, halo);
+
+        for tile in &tiles {
+            let (ox, oy) = tile.interior_offset;
+            let (iw, ih) = tile.interior_extent;
+
+            let expected_padded_x0 = ox.saturating_sub(halo);
+            let expected_padded_y0 = oy.saturating_sub(halo);
+            assert_eq!(tile.padded_offset, (expected_padded_x0, expected_padded_y0));
+
+            let expected_padded_x1 = (ox + iw + halo).min(width);
+            let expected_padded_y1 = (oy + ih + halo).min(height);
+            assert_eq!(tile.padded_extent.width, expected_padded_x1 - expected_padded_x0);
+            assert_eq!(tile.padded_extent.height, expected_padded_y1 - expected_padded_y0);
+        }
+    }
+
+    #[test]
+    fn interior_offset_in_tile_is_relative_to_padded_offset() {
+        let tiles = tile_layout(100, 100, 32, 8);
+        for tile in &tiles {
+            let (local_x, local_y) = tile.interior_offset_in_tile();
+            assert_eq!(local_x, tile.interior_offset.0 - tile.padded_offset.0);
+            assert_eq!(local_y, tile.interior_offset.1 - tile.padded_offset.1);
+        }
+    }
+}
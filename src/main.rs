@@ -1,11 +1,21 @@
+pub mod compute_context;
+pub mod decision_tree;
+pub mod error;
 pub mod feature_extractor_pipeline;
 pub mod util;
 pub mod wgsl;
-pub mod decision_tree;
 
+use compute_context::ComputeContext;
 use decision_tree::RandomForest;
-use feature_extractor_pipeline::{kernel::gaussian_blur::GaussianBlur, pipeline::FeatureExtractorPipeline};
-use pollster::FutureExt;
+use feature_extractor_pipeline::{
+    kernel::{
+        difference_of_gaussians::DifferenceOfGaussians, gaussian_blur::GaussianBlur,
+        gradient_magnitude::GradientMagnitude, hessian_eigenvalues::{EigenSlot, HessianEigenvalues},
+        laplacian_of_gaussian::LaplacianOfGaussian, structure_tensor_eigenvalues::StructureTensorEigenvalues,
+        FeatureKernel,
+    },
+    pipeline::FeatureExtractorPipeline,
+};
 use rand::RngCore;
 use util::{copy_bytes, timeit, ImageBufferExt, WorkgroupSize};
 use wgpu::Extent3d;
@@ -13,65 +23,30 @@ use wgpu::Extent3d;
 use clap::Parser;
 
 
+/// Native convenience wrapper around [`make_pipeline_async`]; unavailable on
+/// `wasm32-unknown-unknown`, where the WebGPU futures it awaits only resolve
+/// on the browser's event loop.
+#[cfg(not(target_arch = "wasm32"))]
 fn make_pipeline<const KSIDE: usize>(
     forest: &RandomForest,
-    kernels: Vec<GaussianBlur<KSIDE>>,
+    kernels: Vec<Box<dyn FeatureKernel<KSIDE>>>,
     img_extent: Extent3d,
-) -> FeatureExtractorPipeline<KSIDE> {
-    // We first initialize an wgpu `Instance`, which contains any "global" state wgpu needs.
-    //
-    // This is what loads the vulkan/dx12/metal/opengl libraries.
-    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor{
-        // flags: wgpu::InstanceFlags::debugging(),
-        ..Default::default()
-    });
-
-    // We then create an `Adapter` which represents a physical gpu in the system. It allows
-    // us to query information about it and create a `Device` from it.
-    //
-    // This function is asynchronous in WebGPU, so request_adapter returns a future. On native/webgl
-    // the future resolves immediately, so we can block on it without harm.
-    let adapter = instance.request_adapter(
-            &wgpu::RequestAdapterOptions{
-                power_preference: wgpu::PowerPreference::HighPerformance,
-                ..Default::default()
-            }
-        )
-        .block_on()
-        .expect("Failed to create adapter");
-
-    // Print out some basic information about the adapter.
-    println!("Running on Adapter: {:#?}", adapter.get_info());
-
-    // Check to see if the adapter supports compute shaders. While WebGPU guarantees support for
-    // compute shaders, wgpu supports a wider range of devices through the use of "downlevel" devices.
-    let downlevel_capabilities = adapter.get_downlevel_capabilities();
-    if !downlevel_capabilities
-        .flags
-        .contains(wgpu::DownlevelFlags::COMPUTE_SHADERS)
-    {
-        panic!("Adapter does not support compute shaders");
-    }
-
-    // We then create a `Device` and a `Queue` from the `Adapter`.
-    //
-    // The `Device` is used to create and manage GPU resources.
-    // The `Queue` is a queue used to submit work for the GPU to process.
-    let (device, queue) = adapter.request_device(
-        &wgpu::DeviceDescriptor {
-            label: None,
-            required_features: wgpu::Features::empty(),
-            required_limits: wgpu::Limits::downlevel_defaults(),
-            memory_hints: wgpu::MemoryHints::MemoryUsage,
-            trace: wgpu::Trace::Off,
-        },
-    )
-    .block_on()
-    .expect("Failed to create device");
+) -> anyhow::Result<FeatureExtractorPipeline<KSIDE>> {
+    pollster::block_on(make_pipeline_async(forest, kernels, img_extent))
+}
 
-    FeatureExtractorPipeline::new(
-        device,
-        queue,
+async fn make_pipeline_async<const KSIDE: usize>(
+    forest: &RandomForest,
+    kernels: Vec<Box<dyn FeatureKernel<KSIDE>>>,
+    img_extent: Extent3d,
+) -> anyhow::Result<FeatureExtractorPipeline<KSIDE>> {
+    // Reuses the process-wide device/queue instead of re-probing
+    // Vulkan/Metal/DX12 on every call; see `ComputeContext` for the
+    // adapter preference order and fallback behavior.
+    let context = ComputeContext::shared_async().await?;
+
+    Ok(FeatureExtractorPipeline::from_context(
+        context,
         WorkgroupSize{
             x: 16,
             y: 16,
@@ -80,10 +55,10 @@ fn make_pipeline<const KSIDE: usize>(
         kernels,
         forest,
         img_extent,
-    )
+    ))
 }
 
-fn main() {
+fn main() -> anyhow::Result<()> {
     const KERNEL_SIDE: usize = 73;
 
     // wgpu uses `log` for all of our logging, so we initialize a logger with the `env_logger` crate.
@@ -130,23 +105,30 @@ fn main() {
     let dims = image.dimensions();
     println!("Image has these dimensions: {:?} ", dims);
 
-    let kernels = vec![
+    let kernels: Vec<Box<dyn FeatureKernel<KERNEL_SIDE>>> = vec![
         // 0.3, 0.7, 0.9, 1.0, 1.6, 3.5, 4.0, 5.0, 7.0, 10.0
-        GaussianBlur::<KERNEL_SIDE>{ sigma: 0.3 },
-        GaussianBlur::<KERNEL_SIDE>{ sigma: 0.7 },
-        GaussianBlur::<KERNEL_SIDE>{ sigma: 0.9, },
-        GaussianBlur::<KERNEL_SIDE>{ sigma: 1.0, },
-        GaussianBlur::<KERNEL_SIDE>{ sigma: 1.6 },
-        GaussianBlur::<KERNEL_SIDE>{ sigma: 3.5 },
-        GaussianBlur::<KERNEL_SIDE>{ sigma: 4.0 },
-        GaussianBlur::<KERNEL_SIDE>{ sigma: 5.0 },
-        GaussianBlur::<KERNEL_SIDE>{ sigma: 7.0 },
-        GaussianBlur::<KERNEL_SIDE>{ sigma: 10.0 },
+        Box::new(GaussianBlur::<KERNEL_SIDE>{ sigma: 0.3 }),
+        Box::new(GaussianBlur::<KERNEL_SIDE>{ sigma: 0.7 }),
+        Box::new(GaussianBlur::<KERNEL_SIDE>{ sigma: 0.9, }),
+        Box::new(GaussianBlur::<KERNEL_SIDE>{ sigma: 1.0, }),
+        Box::new(GaussianBlur::<KERNEL_SIDE>{ sigma: 1.6 }),
+        Box::new(GaussianBlur::<KERNEL_SIDE>{ sigma: 3.5 }),
+        Box::new(GaussianBlur::<KERNEL_SIDE>{ sigma: 4.0 }),
+        Box::new(GaussianBlur::<KERNEL_SIDE>{ sigma: 5.0 }),
+        Box::new(GaussianBlur::<KERNEL_SIDE>{ sigma: 7.0 }),
+        Box::new(GaussianBlur::<KERNEL_SIDE>{ sigma: 10.0 }),
+        Box::new(DifferenceOfGaussians{ sigma_a: 1.0, sigma_b: 1.6 }),
+        Box::new(GradientMagnitude{ sigma: 1.6 }),
+        Box::new(LaplacianOfGaussian{ sigma: 1.6 }),
+        Box::new(HessianEigenvalues{ sigma: 1.6, slot: EigenSlot::Major }),
+        Box::new(HessianEigenvalues{ sigma: 1.6, slot: EigenSlot::Minor }),
+        Box::new(StructureTensorEigenvalues{ sigma: 1.6, integration_sigma: 2.0, slot: EigenSlot::Major }),
+        Box::new(StructureTensorEigenvalues{ sigma: 1.6, integration_sigma: 2.0, slot: EigenSlot::Minor }),
     ];
 
     let num_kernels = kernels.len();
 
-    let pipeline = make_pipeline(&forest, kernels.clone(), image.extent());
+    let pipeline = make_pipeline(&forest, kernels, image.extent())?;
 
     let width = image.width();
     let height = image.height();
@@ -166,4 +148,6 @@ fn main() {
     let parsed = image::ImageBuffer::<image::Rgba<u8>, _>::from_raw(width, height, rgba_u8)
         .expect("Could not parse rgba u8 image!!!");
     parsed.save(format!("./bench/out/segmentation_gpu.png")).unwrap();
+
+    Ok(())
 }
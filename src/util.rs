@@ -0,0 +1,44 @@
+use std::time::Instant;
+
+use image::{ImageBuffer, Pixel};
+use wgpu::Extent3d;
+
+/// Number of invocations per workgroup dimension for a compute dispatch.
+#[derive(Clone, Copy, Debug)]
+pub struct WorkgroupSize {
+    pub x: u32,
+    pub y: u32,
+    pub z: u32,
+}
+
+/// Runs `f`, printing `label` and its wall-clock duration, and returns its result.
+pub fn timeit<T>(label: &str, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = f();
+    println!("{label}: {:?}", start.elapsed());
+    result
+}
+
+/// Reinterprets a raw byte buffer as a `Vec<T>`, timing the copy under `label`.
+pub fn copy_bytes<T: bytemuck::Pod>(bytes: &[u8], label: &str) -> Vec<T> {
+    timeit(label, || bytemuck::cast_slice(bytes).to_vec())
+}
+
+pub trait ImageBufferExt {
+    /// The `wgpu::Extent3d` a texture would need to hold this image, one layer deep.
+    fn extent(&self) -> Extent3d;
+}
+
+impl<P, Container> ImageBufferExt for ImageBuffer<P, Container>
+where
+    P: Pixel + 'static,
+    Container: std::ops::Deref<Target = [P::Subpixel]>,
+{
+    fn extent(&self) -> Extent3d {
+        Extent3d {
+            width: self.width(),
+            height: self.height(),
+            depth_or_array_layers: 1,
+        }
+    }
+}
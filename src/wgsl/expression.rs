@@ -19,6 +19,15 @@ impl<T: ShaderTypeExt> Expression<T> {
     fn new(code: String) -> Self {
         return Self(code, PhantomData);
     }
+
+    /// Wraps an arbitrary, already-valid WGSL fragment as an `Expression<T>`.
+    ///
+    /// Escape hatch for callers outside this module (e.g. feature kernels)
+    /// that need to emit expressions this builder has no dedicated method
+    /// for yet, such as indexing into a storage buffer.
+    pub fn raw(code: impl Into<String>) -> Self {
+        Self::new(code.into())
+    }
 }
 
 impl<T: ShaderTypeExt> Wgsl for Expression<T> {
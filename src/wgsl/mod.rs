@@ -0,0 +1,36 @@
+pub mod expression;
+
+pub use expression::Expression;
+
+pub type FVec3 = nalgebra::Vector3<f32>;
+pub type FVec4 = nalgebra::Vector4<f32>;
+pub type IVec2 = nalgebra::Vector2<i32>;
+
+/// Anything that can render itself as a fragment of WGSL source.
+pub trait Wgsl {
+    fn wgsl(&self) -> String;
+}
+
+/// Maps a Rust-side scalar/vector type onto the WGSL type name used to declare it.
+pub trait ShaderTypeExt {
+    fn wgsl_type_name() -> &'static str;
+}
+
+impl ShaderTypeExt for f32 {
+    fn wgsl_type_name() -> &'static str { "f32" }
+}
+impl ShaderTypeExt for u32 {
+    fn wgsl_type_name() -> &'static str { "u32" }
+}
+impl ShaderTypeExt for i32 {
+    fn wgsl_type_name() -> &'static str { "i32" }
+}
+impl ShaderTypeExt for IVec2 {
+    fn wgsl_type_name() -> &'static str { "vec2<i32>" }
+}
+impl ShaderTypeExt for FVec3 {
+    fn wgsl_type_name() -> &'static str { "vec3<f32>" }
+}
+impl ShaderTypeExt for FVec4 {
+    fn wgsl_type_name() -> &'static str { "vec4<f32>" }
+}